@@ -1,4 +1,8 @@
 use crossterm::event::{self, Event, KeyCode};
+use crossterm::execute;
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
 use ratatui::{
     prelude::Backend,
     style::Stylize,
@@ -6,24 +10,47 @@ use ratatui::{
     widgets::{ListItem, ListState},
     Terminal,
 };
+use std::collections::HashSet;
+use std::env;
+use std::fs;
 use std::fs::File;
 use std::io::Read;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime};
 use thiserror::Error;
 
+use crate::settings::{ListItemFields, Settings};
 use crate::ui;
 
 #[derive(Default)]
 pub struct SshConf {
     pub confs: Vec<Host>,
     pub state: ListState,
+    // Top-level lines (blank lines, comments, `Include` directives) that
+    // come after the last `Host` block of a file, or make up the whole
+    // file if it has no `Host` blocks at all, keyed by that file's path.
+    // Lines that precede a `Host` block instead are kept on that block's
+    // `Host::preceding`, so write-back restores them in their original
+    // position rather than hoisting everything to the top of the file.
+    trailing_top_level: Vec<(PathBuf, Vec<String>)>,
 }
 
 impl std::fmt::Display for SshConf {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for host in &self.confs {
+            for line in &host.preceding {
+                writeln!(f, "{}", line)?;
+            }
             // always display host_id
             writeln!(f, "{}", host)?;
         }
+        for (_, lines) in &self.trailing_top_level {
+            for line in lines {
+                writeln!(f, "{}", line)?;
+            }
+        }
         Ok(())
     }
 }
@@ -38,8 +65,6 @@ impl std::fmt::Display for Host {
             ($field:expr, $label:literal) => {
                 if let Some(value) = $field {
                     writeln!(f, "    {} {}", $label, value)?;
-                } else {
-                    writeln!(f, "    {} none", $label)?;
                 }
             };
         }
@@ -51,6 +76,12 @@ impl std::fmt::Display for Host {
         write_if_some!(&self.local_forward, "localforward");
         write_if_some!(&self.id_file, "identityfile");
 
+        // unknown directives, inline comments and blank separators, verbatim
+        // and in their original order, after all the recognized fields
+        for line in &self.extras {
+            writeln!(f, "{}", line)?;
+        }
+
         Ok(())
     }
 }
@@ -83,6 +114,17 @@ pub struct Host {
     local_forward: Option<String>,
     id_file: Option<String>,
     expanded: bool,
+    // Unknown keywords, inline comments and blank separators found inside
+    // this host's block, kept verbatim and in their original order.
+    extras: Vec<String>,
+    // Top-level lines (blank lines, comments, `Include` directives) that
+    // appeared immediately before this host's `Host` line, within the same
+    // origin file, kept verbatim so write-back restores them in position
+    // instead of hoisting them to the top of the file.
+    preceding: Vec<String>,
+    // File this host was parsed from (the main config, or an `Include`d
+    // file), so edits and new entries get written back to the right place.
+    origin: PathBuf,
 }
 
 enum SshSetting {
@@ -108,24 +150,136 @@ impl SshSetting {
     }
 }
 
+// Score `candidate` against `query` as a fuzzy subsequence match: every char
+// of `query` must appear in `candidate` in order. Matches right after a
+// `.`/`-`/`_` boundary score higher, and the gap between two consecutive
+// matches shrinks their bonus the further apart they are (an immediately
+// adjacent match scores the most), so "hst" ranks "my-host" (a tight match
+// inside "host") above "hoisted-thing" (the same letters, spread wider).
+// Returns `None` if query isn't a subsequence.
+fn fuzzy_score(query: &str, candidate: &str) -> Option<i32> {
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let query_chars: Vec<char> = query.to_lowercase().chars().collect();
+    let cand_chars: Vec<char> = candidate.to_lowercase().chars().collect();
+
+    let mut score = 0;
+    let mut qi = 0;
+    let mut last_matched_ci: Option<usize> = None;
+    for (ci, &c) in cand_chars.iter().enumerate() {
+        if qi >= query_chars.len() {
+            break;
+        }
+        if c == query_chars[qi] {
+            score += 1;
+            if let Some(last_ci) = last_matched_ci {
+                let gap = (ci - last_ci - 1) as i32;
+                score += (5 - gap).max(0);
+            }
+            if ci == 0 || matches!(cand_chars[ci - 1], '.' | '-' | '_') {
+                score += 3;
+            }
+            last_matched_ci = Some(ci);
+            qi += 1;
+        }
+    }
+
+    if qi == query_chars.len() {
+        Some(score)
+    } else {
+        None
+    }
+}
+
+// Best fuzzy score for `host` across the fields a user is likely to search
+// by: alias, hostname and user.
+fn host_score(query: &str, host: &Host) -> Option<i32> {
+    [
+        Some(host.host_id.as_str()),
+        host.host_name.as_deref(),
+        host.user.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    .filter_map(|candidate| fuzzy_score(query, candidate))
+    .max()
+}
+
 impl SshConf {
     pub fn new() -> SshConf {
         SshConf {
             confs: Vec::new(),
             state: ListState::default(),
+            trailing_top_level: Vec::new(),
         }
     }
 
-    fn parse(content: &str) -> Result<SshConf, SshConfError> {
+    pub fn parse(content: &str, origin: &Path) -> Result<SshConf, SshConfError> {
+        let mut visited = HashSet::new();
+        visited.insert(fs::canonicalize(origin).unwrap_or_else(|_| origin.to_path_buf()));
+        Self::parse_with_visited(content, origin, &mut visited)
+    }
+
+    fn parse_with_visited(
+        content: &str,
+        origin: &Path,
+        visited: &mut HashSet<PathBuf>,
+    ) -> Result<SshConf, SshConfError> {
         let mut confs = Vec::new();
+        // Top-level lines seen since the last Host block (or since the
+        // start of the file), waiting to be attached as the `preceding`
+        // lines of whichever Host comes next.
+        let mut pending_top_level = Vec::new();
+        let mut trailing_top_level = Vec::new();
 
         let mut host: Option<Host> = None;
-        for (line_num, line) in content.lines().enumerate() {
-            let line = line.trim();
+        for (line_num, raw_line) in content.lines().enumerate() {
+            let line = raw_line.trim();
             let host_id;
 
-            // Skip empty lines and comments
+            // Preserve empty lines and comments instead of discarding them:
+            // inside a host block they become part of its extras, at the top
+            // level they're buffered to attach to whichever Host comes next.
             if line.is_empty() || line.starts_with('#') {
+                match &mut host {
+                    Some(h) => h.extras.push(raw_line.to_string()),
+                    None => pending_top_level.push(raw_line.to_string()),
+                }
+                continue;
+            }
+
+            let include_str = "Include ";
+            // Handle Include directives: splice the referenced file(s)' hosts
+            // in at this point. Recognized regardless of whether we're
+            // currently inside a Host block: an `Include` between (or after)
+            // blocks must not fall through to the "unknown directive inside
+            // this host" branch below, so close out any open host first, the
+            // same as a `Host` line would.
+            if line.starts_with(include_str) {
+                if let Some(h) = host.take() {
+                    confs.push(h);
+                }
+
+                // Keep the directive itself so write-back doesn't drop it,
+                // in position relative to the hosts around it rather than
+                // hoisted to the top of the file.
+                pending_top_level.push(raw_line.to_string());
+
+                let pattern = line[include_str.len()..].trim();
+                for path in Self::expand_include_pattern(pattern) {
+                    let canonical = fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+                    if !visited.insert(canonical) {
+                        continue; // already parsed this file: include cycle
+                    }
+                    let Ok(included_content) = fs::read_to_string(&path) else {
+                        continue; // referenced file doesn't exist (yet)
+                    };
+                    let included = Self::parse_with_visited(&included_content, &path, visited)?;
+                    confs.extend(included.confs);
+                    trailing_top_level.extend(included.trailing_top_level);
+                }
                 continue;
             }
 
@@ -153,6 +307,9 @@ impl SshConf {
                     local_forward: None,
                     id_file: None,
                     expanded: false,
+                    extras: Vec::new(),
+                    preceding: std::mem::take(&mut pending_top_level),
+                    origin: origin.to_path_buf(),
                 })
             }
             // Handle settings
@@ -181,10 +338,9 @@ impl SshConf {
                         SshSetting::Port => host.port = parts[1].parse().ok(),
                     };
                 } else {
-                    return Err(SshConfError::ParseError(
-                        line_num + 1,
-                        "Invalid config line format".to_string(),
-                    ));
+                    // Unknown keyword (e.g. ForwardAgent, ServerAliveInterval):
+                    // keep it verbatim so write-back doesn't drop it.
+                    host.extras.push(raw_line.to_string());
                 }
             } else {
                 return Err(SshConfError::ParseError(
@@ -199,61 +355,431 @@ impl SshConf {
             confs.push(host);
         }
 
+        // Anything left over never got attached to a following Host: it's
+        // either trailing lines after the last block, or the whole file if
+        // it has no Host blocks at all.
+        if !pending_top_level.is_empty() {
+            trailing_top_level.push((origin.to_path_buf(), pending_top_level));
+        }
+
         Ok(SshConf {
             confs,
             state: ListState::default(),
+            trailing_top_level,
         })
     }
+
+    /// Parse a buffer expected to contain exactly one `Host` block, used for
+    /// the editor round-trip where a single entry is serialized, edited and
+    /// read back.
+    fn parse_single(content: &str, origin: &Path) -> Result<Host, SshConfError> {
+        let mut conf = SshConf::parse(content, origin)?;
+        match conf.confs.len() {
+            1 => Ok(conf.confs.remove(0)),
+            0 => Err(SshConfError::ParseError(0, "No host found".to_string())),
+            _ => Err(SshConfError::ParseError(
+                0,
+                "Expected exactly one host".to_string(),
+            )),
+        }
+    }
+
+    // Resolve an `Include` pattern to the file(s) it refers to: `~`
+    // expansion, bare names resolved relative to `~/.ssh` (matching
+    // OpenSSH), and a trailing glob like `config.d/*`.
+    fn expand_include_pattern(pattern: &str) -> Vec<PathBuf> {
+        let expanded = if let Some(rest) = pattern.strip_prefix("~/") {
+            PathBuf::from(Self::home_dir()).join(rest)
+        } else if Path::new(pattern).is_absolute() {
+            PathBuf::from(pattern)
+        } else {
+            Self::home_ssh_dir().join(pattern)
+        };
+
+        if !expanded.to_string_lossy().contains('*') {
+            return vec![expanded];
+        }
+
+        let dir = expanded
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| PathBuf::from("."));
+        let name_pattern = expanded
+            .file_name()
+            .map(|f| f.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let mut matches: Vec<PathBuf> = fs::read_dir(&dir)
+            .into_iter()
+            .flatten()
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.file_name()
+                    .map(|name| Self::glob_match(&name_pattern, &name.to_string_lossy()))
+                    .unwrap_or(false)
+            })
+            .collect();
+        matches.sort();
+        matches
+    }
+
+    // Minimal shell-style glob match: `*` matches any run of characters,
+    // every other character must match literally.
+    fn glob_match(pattern: &str, text: &str) -> bool {
+        fn matches(pattern: &[u8], text: &[u8]) -> bool {
+            match pattern.first() {
+                None => text.is_empty(),
+                Some(b'*') => {
+                    matches(&pattern[1..], text)
+                        || (!text.is_empty() && matches(pattern, &text[1..]))
+                }
+                Some(p) => text.first() == Some(p) && matches(&pattern[1..], &text[1..]),
+            }
+        }
+        matches(pattern.as_bytes(), text.as_bytes())
+    }
+
+    fn home_dir() -> String {
+        env::var("HOME").unwrap_or_else(|_| "/".to_string())
+    }
+
+    fn home_ssh_dir() -> PathBuf {
+        PathBuf::from(Self::home_dir()).join(".ssh")
+    }
+}
+
+// Which field the "new host" wizard is currently prompting for.
+enum AddStage {
+    Alias,
+    Target,
+    IdentityFile,
+}
+
+// In-progress state for the `a` "new host" wizard: fields confirmed so far,
+// plus the line currently being typed.
+struct AddWizard {
+    stage: AddStage,
+    input: String,
+    alias: String,
+    user: String,
+    host_name: String,
+    port: Option<u32>,
+}
+
+impl AddWizard {
+    fn new() -> AddWizard {
+        AddWizard {
+            stage: AddStage::Alias,
+            input: String::new(),
+            alias: String::new(),
+            user: String::new(),
+            host_name: String::new(),
+            port: None,
+        }
+    }
+
+    fn prompt(&self) -> &'static str {
+        match self.stage {
+            AddStage::Alias => "New host alias",
+            AddStage::Target => "user@host[:port]",
+            AddStage::IdentityFile => "IdentityFile (optional)",
+        }
+    }
 }
 
-#[derive(Default)]
 pub struct App {
     pub confs: SshConf,
+    pub error_msg: Option<String>,
+    pub search_mode: bool,
+    pub search_query: String,
+    pub settings: Settings,
+    add_wizard: Option<AddWizard>,
     selected_id: Option<String>,
+    config_path: String,
+    // Last-seen mtime of every file currently backing a host (the main
+    // config and any `Include`d files), so editing a config.d-style
+    // included file externally is also picked up by `reload_if_changed`.
+    last_mtimes: Vec<(PathBuf, Option<SystemTime>)>,
 }
 
 impl App {
     pub fn new() -> App {
         App {
             confs: SshConf::new(),
+            error_msg: None,
+            search_mode: false,
+            search_query: String::new(),
+            settings: Settings::load(),
+            add_wizard: None,
             selected_id: None,
+            config_path: String::new(),
+            last_mtimes: Vec::new(),
+        }
+    }
+
+    /// Arguments `ssh` is launched with, from the ssui settings file if one
+    /// is present, defaulting to the original `-t`.
+    pub fn ssh_args(&self) -> Vec<String> {
+        self.settings.ssh_args()
+    }
+
+    /// Footer line for the "new host" wizard while it's active, e.g.
+    /// `"New host alias: my-server"`.
+    pub fn add_wizard_line(&self) -> Option<String> {
+        self.add_wizard
+            .as_ref()
+            .map(|w| format!("{}: {}", w.prompt(), w.input))
+    }
+
+    /// Indices into `confs.confs` for the hosts currently shown in the list,
+    /// in display order. With no active search query this is every host in
+    /// its original order; otherwise it's the fuzzy matches sorted by
+    /// descending score.
+    pub fn visible_indices(&self) -> Vec<usize> {
+        if self.search_query.is_empty() {
+            return (0..self.confs.confs.len()).collect();
         }
+
+        let mut scored: Vec<(usize, i32)> = self
+            .confs
+            .confs
+            .iter()
+            .enumerate()
+            .filter_map(|(i, host)| host_score(&self.search_query, host).map(|score| (i, score)))
+            .collect();
+        scored.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+        scored.into_iter().map(|(i, _)| i).collect()
     }
 
-    // Populate confs attribute
-    pub fn read_ssh_conf(self: &mut App) -> Result<(), Box<dyn std::error::Error>> {
+    // Map the currently highlighted list row back to its index in `confs.confs`.
+    fn selected_real_index(&self) -> Option<usize> {
+        let i = self.confs.state.selected()?;
+        self.visible_indices().get(i).copied()
+    }
+
+    // Populate confs attribute. `cli_config_path` takes priority, then
+    // `$SSH_CONFIG`, then the default `~/.ssh/config`.
+    pub fn read_ssh_conf(
+        self: &mut App,
+        cli_config_path: Option<&str>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
         // TODO Better error handling on opening file
-        let mut file = File::open("/home/edvin/.ssh/config")?;
+        self.config_path = Self::resolve_config_path(cli_config_path);
+        let mut file = File::open(&self.config_path)?;
         let mut contents = String::new();
         file.read_to_string(&mut contents)?;
-        self.confs = SshConf::parse(&contents)?;
+        self.confs = SshConf::parse(&contents, Path::new(&self.config_path))?;
+        self.last_mtimes = self.snapshot_mtimes();
 
         Ok(())
     }
 
-    pub fn run<B: Backend>(
+    // Every file currently backing a host: the main config, plus any
+    // `Include`d files hosts were parsed from.
+    fn origin_paths(&self) -> Vec<PathBuf> {
+        let mut paths = vec![PathBuf::from(&self.config_path)];
+        for host in &self.confs.confs {
+            if !paths.contains(&host.origin) {
+                paths.push(host.origin.clone());
+            }
+        }
+        paths
+    }
+
+    // Current mtime of every origin path, for change detection in
+    // `reload_if_changed`.
+    fn snapshot_mtimes(&self) -> Vec<(PathBuf, Option<SystemTime>)> {
+        self.origin_paths()
+            .into_iter()
+            .map(|path| {
+                let mtime = fs::metadata(&path).ok().and_then(|m| m.modified().ok());
+                (path, mtime)
+            })
+            .collect()
+    }
+
+    fn resolve_config_path(cli_config_path: Option<&str>) -> String {
+        if let Some(path) = cli_config_path {
+            return path.to_string();
+        }
+        if let Ok(path) = env::var("SSH_CONFIG") {
+            return path;
+        }
+        SshConf::home_ssh_dir()
+            .join("config")
+            .to_string_lossy()
+            .to_string()
+    }
+
+    // Rewrite every file a host was parsed from (the main config and any
+    // `Include`d files) from the current in-memory state, so edits land in
+    // the file the host actually came from.
+    fn write_ssh_conf(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        use std::fmt::Write as _;
+
+        // Index of `path`'s buffer in `by_origin`, creating an empty one if
+        // this is the first line seen for that file.
+        fn buf_index(by_origin: &mut Vec<(PathBuf, String)>, path: &Path) -> usize {
+            match by_origin.iter().position(|(p, _)| p == path) {
+                Some(i) => i,
+                None => {
+                    by_origin.push((path.to_path_buf(), String::new()));
+                    by_origin.len() - 1
+                }
+            }
+        }
+
+        let mut by_origin: Vec<(PathBuf, String)> = Vec::new();
+
+        for host in &self.confs.confs {
+            let i = buf_index(&mut by_origin, &host.origin);
+            let buf = &mut by_origin[i].1;
+            for line in &host.preceding {
+                writeln!(buf, "{}", line)?;
+            }
+            writeln!(buf, "{}", host)?;
+        }
+
+        for (path, lines) in &self.confs.trailing_top_level {
+            let i = buf_index(&mut by_origin, path);
+            let buf = &mut by_origin[i].1;
+            for line in lines {
+                writeln!(buf, "{}", line)?;
+            }
+        }
+
+        for (path, content) in &by_origin {
+            fs::write(path, content)?;
+        }
+
+        self.last_mtimes = self.snapshot_mtimes();
+        Ok(())
+    }
+
+    // If any origin file's mtime has moved since we last read it, re-parse
+    // the main config and refresh the list, keeping the current selection on
+    // the same `host_id` where possible. This lets the config be edited
+    // externally (in another pane, or by a future watcher) while ssui stays
+    // open, including when the edit lands in an `Include`d file rather than
+    // the main one.
+    fn reload_if_changed(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        let current_mtimes = self.snapshot_mtimes();
+        if current_mtimes == self.last_mtimes {
+            return Ok(());
+        }
+
+        let selected_host_id = self
+            .selected_real_index()
+            .map(|i| self.confs.confs[i].host_id.clone());
+
+        let mut contents = String::new();
+        File::open(&self.config_path)?.read_to_string(&mut contents)?;
+        self.confs = SshConf::parse(&contents, Path::new(&self.config_path))?;
+        self.last_mtimes = self.snapshot_mtimes();
+
+        if let Some(host_id) = selected_host_id {
+            // The list state indexes visible rows, which differ from
+            // `confs.confs` indices while a search filter is active.
+            let pos = self
+                .visible_indices()
+                .into_iter()
+                .position(|i| self.confs.confs[i].host_id == host_id);
+            self.confs.state.select(pos);
+        }
+
+        Ok(())
+    }
+
+    pub fn run<B: Backend + Write>(
         &mut self,
         terminal: &mut Terminal<B>,
     ) -> Result<String, Box<dyn std::error::Error>> {
+        let poll_interval = Duration::from_millis(250);
         loop {
             terminal.draw(|frame| ui::render(frame, self))?;
-            if self.handle_keys()? {
-                if let Some(selected) = &self.selected_id {
-                    return Ok(selected.to_string());
-                } else {
-                    return Err(color_eyre::eyre::eyre!("No ssh config selected!").into());
+
+            if event::poll(poll_interval)? {
+                if self.handle_keys(terminal)? {
+                    if let Some(selected) = &self.selected_id {
+                        return Ok(selected.to_string());
+                    } else {
+                        return Err(color_eyre::eyre::eyre!("No ssh config selected!").into());
+                    }
                 }
+            } else {
+                self.reload_if_changed()?;
             }
         }
     }
 
-    fn handle_keys(&mut self) -> Result<bool, Box<dyn std::error::Error>> {
+    fn handle_keys<B: Backend + Write>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+    ) -> Result<bool, Box<dyn std::error::Error>> {
         if let Event::Key(k) = event::read()? {
             if k.kind != event::KeyEventKind::Press {
                 return Ok(false);
             }
+
+            if self.search_mode {
+                match k.code {
+                    KeyCode::Esc => {
+                        self.search_mode = false;
+                        self.search_query.clear();
+                        self.confs.state.select_first();
+                    }
+                    KeyCode::Enter => {
+                        if let Some(i) = self.selected_real_index() {
+                            self.selected_id = Some(self.confs.confs[i].host_id.clone());
+                        };
+                        self.search_mode = false;
+                        return Ok(true);
+                    }
+                    KeyCode::Backspace => {
+                        self.search_query.pop();
+                        self.confs.state.select_first();
+                    }
+                    KeyCode::Down => self.confs.state.select_next(),
+                    KeyCode::Up => self.confs.state.select_previous(),
+                    KeyCode::Char(c) => {
+                        self.search_query.push(c);
+                        self.confs.state.select_first();
+                    }
+                    _ => {}
+                }
+                return Ok(false);
+            }
+
+            if self.add_wizard.is_some() {
+                match k.code {
+                    KeyCode::Esc => {
+                        self.add_wizard = None;
+                        self.error_msg = None;
+                    }
+                    KeyCode::Enter => self.advance_add_wizard()?,
+                    KeyCode::Backspace => {
+                        self.add_wizard.as_mut().unwrap().input.pop();
+                    }
+                    KeyCode::Char(c) => {
+                        self.add_wizard.as_mut().unwrap().input.push(c);
+                    }
+                    _ => {}
+                }
+                return Ok(false);
+            }
+
             match k.code {
                 KeyCode::Char('q') | KeyCode::Esc => return Ok(true),
+                KeyCode::Char('/') => {
+                    self.search_mode = true;
+                    self.search_query.clear();
+                    self.confs.state.select_first();
+                }
+                KeyCode::Char('a') => {
+                    self.add_wizard = Some(AddWizard::new());
+                    self.error_msg = None;
+                }
                 KeyCode::Char('h') | KeyCode::Left => self.confs.state.select(None),
                 KeyCode::Char('j') | KeyCode::Down => self.confs.state.select_next(),
                 KeyCode::Char('k') | KeyCode::Up => self.confs.state.select_previous(),
@@ -262,8 +788,11 @@ impl App {
                 KeyCode::Char('l') | KeyCode::Right => {
                     self.expand();
                 }
+                KeyCode::Char('e') => {
+                    self.edit_selected(terminal)?;
+                }
                 KeyCode::Enter => {
-                    if let Some(i) = self.confs.state.selected() {
+                    if let Some(i) = self.selected_real_index() {
                         self.selected_id = Some(self.confs.confs[i].host_id.clone());
                     };
                     return Ok(true);
@@ -275,27 +804,208 @@ impl App {
     }
 
     fn expand(&mut self) {
-        if let Some(i) = self.confs.state.selected() {
+        if let Some(i) = self.selected_real_index() {
             self.confs.confs[i].expanded = !self.confs.confs[i].expanded;
         }
     }
-}
 
-impl From<&Host> for ListItem<'_> {
-    fn from(value: &Host) -> Self {
-        // Create styled main line with host_id
+    // Open the selected host in $EDITOR, re-parse it on return and, if it
+    // parses cleanly, replace the entry and write the config back to disk.
+    fn edit_selected<B: Backend + Write>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(i) = self.selected_real_index() else {
+            return Ok(());
+        };
+
+        let editor = env::var("EDITOR").unwrap_or_else(|_| "vi".to_string());
+        let tmp_path = env::temp_dir().join(format!(
+            "ssui-{}-{}.conf",
+            self.confs.confs[i].host_id,
+            std::process::id()
+        ));
+        fs::write(&tmp_path, self.confs.confs[i].to_string())?;
+
+        disable_raw_mode()?;
+        execute!(
+            terminal.backend_mut(),
+            LeaveAlternateScreen,
+            event::DisableMouseCapture
+        )?;
+
+        let status = Command::new(&editor).arg(&tmp_path).status();
 
+        enable_raw_mode()?;
+        execute!(
+            terminal.backend_mut(),
+            EnterAlternateScreen,
+            event::EnableMouseCapture
+        )?;
+        terminal.clear()?;
+
+        status?;
+
+        let edited = fs::read_to_string(&tmp_path)?;
+        let _ = fs::remove_file(&tmp_path);
+
+        let origin = self.confs.confs[i].origin.clone();
+        match SshConf::parse_single(&edited, &origin) {
+            Ok(mut host) => {
+                // Same alias-uniqueness rule the "new host" wizard enforces:
+                // an edit that renames this host to collide with another
+                // one would otherwise write two Host blocks with the same id.
+                let alias_taken = self
+                    .confs
+                    .confs
+                    .iter()
+                    .enumerate()
+                    .any(|(j, h)| j != i && h.host_id == host.host_id);
+                if alias_taken {
+                    self.error_msg =
+                        Some(format!("Alias \"{}\" is already in use", host.host_id));
+                    return Ok(());
+                }
+
+                // The edit buffer only ever contains this one host's block,
+                // so it can't carry the top-level lines that preceded it in
+                // the real file; keep the ones already on record.
+                host.preceding = std::mem::take(&mut self.confs.confs[i].preceding);
+                self.confs.confs[i] = host;
+                self.error_msg = None;
+                self.write_ssh_conf()?;
+            }
+            Err(e) => {
+                self.error_msg = Some(e.to_string());
+            }
+        }
+
+        Ok(())
+    }
+
+    // Validate the wizard's current input and either move to the next
+    // stage or, on the last stage, build and append the new `Host`.
+    // Invalid input is reported via `error_msg` and re-prompted in place.
+    fn advance_add_wizard(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        let wizard = self.add_wizard.as_mut().unwrap();
+
+        match wizard.stage {
+            AddStage::Alias => {
+                let alias = wizard.input.trim().to_string();
+                if alias.is_empty() {
+                    self.error_msg = Some("Alias must not be empty".to_string());
+                    return Ok(());
+                }
+                if self.confs.confs.iter().any(|h| h.host_id == alias) {
+                    self.error_msg = Some(format!("Alias \"{}\" is already in use", alias));
+                    return Ok(());
+                }
+
+                wizard.alias = alias;
+                wizard.input.clear();
+                wizard.stage = AddStage::Target;
+                self.error_msg = None;
+            }
+            AddStage::Target => {
+                let target = wizard.input.trim();
+                let Some((user, host_part)) = target.split_once('@') else {
+                    self.error_msg = Some("Expected user@host[:port]".to_string());
+                    return Ok(());
+                };
+
+                let (host_name, port) = match host_part.split_once(':') {
+                    Some((host_name, port_str)) => match port_str.parse::<u32>() {
+                        Ok(port) => (host_name, Some(port)),
+                        Err(_) => {
+                            self.error_msg = Some(format!("Invalid port \"{}\"", port_str));
+                            return Ok(());
+                        }
+                    },
+                    None => (host_part, None),
+                };
+
+                if user.is_empty() || host_name.is_empty() {
+                    self.error_msg = Some("Expected user@host[:port]".to_string());
+                    return Ok(());
+                }
+
+                wizard.user = user.to_string();
+                wizard.host_name = host_name.to_string();
+                wizard.port = port;
+                wizard.input.clear();
+                wizard.stage = AddStage::IdentityFile;
+                self.error_msg = None;
+            }
+            AddStage::IdentityFile => {
+                let id_file = wizard.input.trim();
+                let host = Host {
+                    host_id: wizard.alias.clone(),
+                    host_name: Some(wizard.host_name.clone()),
+                    port: wizard.port,
+                    user: Some(wizard.user.clone()),
+                    proxy_jump: None,
+                    local_forward: None,
+                    id_file: if id_file.is_empty() {
+                        None
+                    } else {
+                        Some(id_file.to_string())
+                    },
+                    expanded: false,
+                    extras: Vec::new(),
+                    preceding: Vec::new(),
+                    origin: PathBuf::from(&self.config_path),
+                };
+
+                self.confs.confs.push(host);
+                self.add_wizard = None;
+                self.error_msg = None;
+                self.write_ssh_conf()?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Host {
+    // Build the list row for this host. `fields` controls which details (and
+    // which emoji) show up in the collapsed view; the expanded view always
+    // shows every known field.
+    pub fn to_list_item(&self, fields: &ListItemFields) -> ListItem<'static> {
+        let value = self;
         if !value.expanded {
             // Add host details as spans
             let mut line = Line::from(value.host_id.clone().bold());
             let details = vec![
-                value.host_name.as_ref().map(|n| format!("🖥️ {}", n)),
-                value.user.as_ref().map(|u| format!("👤 {}", u)),
-                value.port.map(|p| format!("🚪 {}", p)),
-                value.proxy_jump.as_ref().map(|p| format!("↗️↗️{}", p)),
+                (
+                    fields.host_name,
+                    value.host_name.as_ref().map(|v| v.to_string()),
+                    &fields.host_name_emoji,
+                ),
+                (
+                    fields.user,
+                    value.user.as_ref().map(|v| v.to_string()),
+                    &fields.user_emoji,
+                ),
+                (
+                    fields.port,
+                    value.port.map(|v| v.to_string()),
+                    &fields.port_emoji,
+                ),
+                (
+                    fields.proxy_jump,
+                    value.proxy_jump.as_ref().map(|v| v.to_string()),
+                    &fields.proxy_jump_emoji,
+                ),
             ]
             .into_iter()
-            .flatten()
+            .filter_map(|(enabled, value, emoji)| {
+                if enabled {
+                    value.map(|v| format!("{} {}", emoji, v))
+                } else {
+                    None
+                }
+            })
             .collect::<Vec<_>>()
             .join("  ");
 
@@ -326,3 +1036,91 @@ impl From<&Host> for ListItem<'_> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzzy_score_requires_subsequence() {
+        assert_eq!(fuzzy_score("xyz", "my-host"), None);
+        assert!(fuzzy_score("hst", "my-host").is_some());
+    }
+
+    #[test]
+    fn fuzzy_score_ranks_contiguous_and_boundary_matches_higher() {
+        // "hst" matches both at a boundary (after `-` in "my-host", at the
+        // start of "hoisted-thing") with "st" adjacent in both, but "my-host"
+        // has its "h" and "s" closer together (one char apart, not two).
+        let boundary = fuzzy_score("hst", "my-host").unwrap();
+        let scattered = fuzzy_score("hst", "hoisted-thing").unwrap();
+        assert!(boundary > scattered);
+    }
+
+    #[test]
+    fn glob_match_matches_star_and_literal_chars() {
+        assert!(SshConf::glob_match("*.conf", "hosts.conf"));
+        assert!(SshConf::glob_match("config.d/*", "config.d/foo"));
+        assert!(!SshConf::glob_match("*.conf", "hosts.txt"));
+    }
+
+    #[test]
+    fn parse_splices_glob_included_hosts_between_host_blocks() {
+        let dir =
+            env::temp_dir().join(format!("ssui-test-include-{}", std::process::id()));
+        let config_d = dir.join("config.d");
+        fs::create_dir_all(&config_d).unwrap();
+        fs::write(
+            config_d.join("middle.conf"),
+            "Host middle\n    HostName mid.example.com\n",
+        )
+        .unwrap();
+
+        let main_path = dir.join("config");
+        let content = format!(
+            "Host a\n    HostName a.example.com\nInclude {}/*\nHost b\n    HostName b.example.com\n",
+            config_d.display()
+        );
+        fs::write(&main_path, &content).unwrap();
+
+        let conf = SshConf::parse(&content, &main_path).unwrap();
+        let ids: Vec<&str> = conf.confs.iter().map(|h| h.host_id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "middle", "b"]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn write_back_keeps_include_after_the_host_block_it_followed() {
+        let dir = env::temp_dir().join(format!("ssui-test-roundtrip-{}", std::process::id()));
+        let included_dir = dir.join("dir");
+        fs::create_dir_all(&included_dir).unwrap();
+        fs::write(
+            included_dir.join("extra.conf"),
+            "Host extra\n    HostName extra.example.com\n",
+        )
+        .unwrap();
+
+        let main_path = dir.join("config");
+        let content = format!(
+            "Host wildcard\n    User root\n\nInclude {}/*\n",
+            included_dir.display()
+        );
+        fs::write(&main_path, &content).unwrap();
+
+        let mut app = App::new();
+        app.config_path = main_path.to_string_lossy().to_string();
+        app.confs = SshConf::parse(&content, &main_path).unwrap();
+        app.write_ssh_conf().unwrap();
+
+        let written = fs::read_to_string(&main_path).unwrap();
+        let wildcard_pos = written.find("host wildcard").unwrap();
+        let include_pos = written.find("Include").unwrap();
+        assert!(
+            wildcard_pos < include_pos,
+            "Include must stay after the host it originally followed, got:\n{written}"
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
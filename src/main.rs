@@ -1,3 +1,4 @@
+use std::env;
 use std::io;
 use std::os::unix::process::CommandExt;
 use std::process::Command;
@@ -5,6 +6,7 @@ use std::process::Command;
 use crate::app::App;
 
 mod app;
+mod settings;
 mod ui;
 
 use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
@@ -20,6 +22,9 @@ use color_eyre::Result;
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     color_eyre::install()?;
 
+    let args: Vec<String> = env::args().collect();
+    let config_path = config_path_arg(&args);
+
     enable_raw_mode()?;
     let mut stderr = io::stderr();
     execute!(stderr, EnterAlternateScreen, EnableMouseCapture)?;
@@ -28,7 +33,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let mut terminal = Terminal::new(backend)?;
 
     let mut app = App::new();
-    app.read_ssh_conf()?;
+    app.read_ssh_conf(config_path.as_deref())?;
     let selected_id = app.run(&mut terminal);
 
     // restore terminal
@@ -40,12 +45,20 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     )?;
     terminal.show_cursor()?;
     if let Ok(id) = selected_id {
-        execute_ssh(id);
+        execute_ssh(id, app.ssh_args());
     }
 
     Ok(())
 }
 
-fn execute_ssh(id: String) {
-    Command::new("ssh").arg("-t").arg(id).exec();
+// Look for `--config <path>` among the CLI args.
+fn config_path_arg(args: &[String]) -> Option<String> {
+    args.iter()
+        .position(|a| a == "--config")
+        .and_then(|i| args.get(i + 1))
+        .cloned()
+}
+
+fn execute_ssh(id: String, ssh_args: Vec<String>) {
+    Command::new("ssh").args(ssh_args).arg(id).exec();
 }
@@ -0,0 +1,80 @@
+use serde::Deserialize;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+/// User-tunable presentation settings, loaded from
+/// `$XDG_CONFIG_HOME/ssui/config.toml` (falling back to
+/// `~/.config/ssui/config.toml`). Every field is optional; anything left out
+/// keeps ssui's built-in default.
+#[derive(Debug, Default, Deserialize)]
+pub struct Settings {
+    #[serde(default)]
+    pub colors: Colors,
+    #[serde(default)]
+    pub ssh_args: Option<Vec<String>>,
+    #[serde(default)]
+    pub list_item: ListItemFields,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct Colors {
+    pub normal_row_bg: Option<String>,
+    pub alt_row_bg: Option<String>,
+    pub selected_bg: Option<String>,
+}
+
+/// Which fields (and their emoji) show in the collapsed `ListItem` row.
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct ListItemFields {
+    pub host_name: bool,
+    pub host_name_emoji: String,
+    pub user: bool,
+    pub user_emoji: String,
+    pub port: bool,
+    pub port_emoji: String,
+    pub proxy_jump: bool,
+    pub proxy_jump_emoji: String,
+}
+
+impl Default for ListItemFields {
+    fn default() -> Self {
+        ListItemFields {
+            host_name: true,
+            host_name_emoji: "🖥️".to_string(),
+            user: true,
+            user_emoji: "👤".to_string(),
+            port: true,
+            port_emoji: "🚪".to_string(),
+            proxy_jump: true,
+            proxy_jump_emoji: "↗️↗️".to_string(),
+        }
+    }
+}
+
+impl Settings {
+    /// Load settings from disk, falling back to defaults if the file is
+    /// missing or fails to parse.
+    pub fn load() -> Settings {
+        Self::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|contents| toml::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    fn path() -> Option<PathBuf> {
+        let config_home = match env::var("XDG_CONFIG_HOME") {
+            Ok(dir) => PathBuf::from(dir),
+            Err(_) => PathBuf::from(env::var("HOME").ok()?).join(".config"),
+        };
+        Some(config_home.join("ssui").join("config.toml"))
+    }
+
+    /// Arguments `ssh` is launched with, defaulting to the original `-t`.
+    pub fn ssh_args(&self) -> Vec<String> {
+        self.ssh_args
+            .clone()
+            .unwrap_or_else(|| vec!["-t".to_string()])
+    }
+}
@@ -7,10 +7,9 @@ use ratatui::{
 
 use crate::app::App;
 
-// Constants
+// Default colors, used unless overridden by the ssui settings file.
 const NORMAL_ROW_BG: Color = SLATE.c950;
 const ALT_ROW_BG_COLOR: Color = SLATE.c900;
-const SELECTED_STYLE: Style = Style::new().bg(SLATE.c800).add_modifier(Modifier::BOLD);
 
 pub fn render(frame: &mut ratatui::Frame<'_>, app: &mut App) {
     let chunks = Layout::default()
@@ -25,7 +24,7 @@ pub fn render(frame: &mut ratatui::Frame<'_>, app: &mut App) {
     // Create title and put into first chunk
     frame.render_widget(generate_header(), chunks[0]);
     frame.render_stateful_widget(generate_list(app), chunks[1], &mut app.confs.state);
-    frame.render_widget(generate_footer(), chunks[2]);
+    frame.render_widget(generate_footer(app), chunks[2]);
 }
 
 fn generate_header() -> Paragraph<'static> {
@@ -41,48 +40,92 @@ fn generate_header() -> Paragraph<'static> {
     title
 }
 
-fn generate_footer() -> Paragraph<'static> {
+fn generate_footer(app: &App) -> Paragraph<'static> {
     let footer_block = Block::default()
         .borders(Borders::TOP)
         .style(Style::default());
 
-    let footer =
-        Paragraph::new("Use ↓↑ to move, ← to unselect, → to change status, g/G to go top/bottom.")
+    if let Some(err) = &app.error_msg {
+        return Paragraph::new(err.clone())
             .centered()
+            .style(Style::default().fg(Color::Red))
             .block(footer_block);
+    }
+
+    if app.search_mode {
+        return Paragraph::new(format!("/{}", app.search_query))
+            .centered()
+            .block(footer_block);
+    }
+
+    if let Some(line) = app.add_wizard_line() {
+        return Paragraph::new(line).centered().block(footer_block);
+    }
+
+    let footer = Paragraph::new(
+        "Use ↓↑ to move, ← to unselect, → to change status, e to edit, a to add, / to search, g/G to go top/bottom.",
+    )
+    .centered()
+    .block(footer_block);
     footer
 }
 
 fn generate_list(app: &App) -> List<'static> {
     let block = Block::new().style(Style::default());
 
-    // Iterate through all elements in the `items` and stylize them.
+    // Iterate through the hosts currently visible (all of them, or the
+    // fuzzy-filtered subset while searching) and stylize them.
 
     let items: Vec<ListItem> = app
-        .confs
-        .confs
-        .iter()
+        .visible_indices()
+        .into_iter()
         .enumerate()
-        .map(|(i, host)| {
-            let color = alternate_colors(i);
-            ListItem::from(host).bg(color)
+        .map(|(display_i, real_i)| {
+            let color = alternate_colors(display_i, app);
+            app.confs.confs[real_i]
+                .to_list_item(&app.settings.list_item)
+                .bg(color)
         })
         .collect();
 
+    let selected_style = Style::new()
+        .bg(resolve_color(
+            app.settings.colors.selected_bg.as_deref(),
+            SLATE.c800,
+        ))
+        .add_modifier(Modifier::BOLD);
+
     // Create a List from all list items and highlight the currently selected one
     let list = List::new(items)
         .block(block)
-        .highlight_style(SELECTED_STYLE)
+        .highlight_style(selected_style)
         .highlight_symbol(">")
         .highlight_spacing(HighlightSpacing::Always);
 
     list
 }
 
-const fn alternate_colors(i: usize) -> Color {
+fn alternate_colors(i: usize, app: &App) -> Color {
     if i % 2 == 0 {
-        NORMAL_ROW_BG
+        resolve_color(app.settings.colors.normal_row_bg.as_deref(), NORMAL_ROW_BG)
     } else {
-        ALT_ROW_BG_COLOR
+        resolve_color(app.settings.colors.alt_row_bg.as_deref(), ALT_ROW_BG_COLOR)
+    }
+}
+
+// Parse a user-supplied `#rrggbb` color, falling back to `default` if it's
+// absent or malformed.
+fn resolve_color(hex: Option<&str>, default: Color) -> Color {
+    hex.and_then(parse_hex_color).unwrap_or(default)
+}
+
+fn parse_hex_color(hex: &str) -> Option<Color> {
+    let hex = hex.trim_start_matches('#');
+    if hex.len() != 6 {
+        return None;
     }
+    let r = u8::from_str_radix(&hex[0..2], 16).ok()?;
+    let g = u8::from_str_radix(&hex[2..4], 16).ok()?;
+    let b = u8::from_str_radix(&hex[4..6], 16).ok()?;
+    Some(Color::Rgb(r, g, b))
 }